@@ -0,0 +1,227 @@
+//! レスポンスをキャッシュする`HttpClient`のデコレータを定義する。
+
+use crate::error::Error;
+use crate::headers::Headers;
+use crate::http_client::HttpClient;
+use crate::response::RawResponse;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// キャッシュに格納する1件分のレスポンスと、その有効期限。
+struct CacheEntry {
+    response: RawResponse,
+    expires_at: Instant,
+}
+
+/// 任意の`HttpClient`をラップし、GETレスポンスをURLごとにキャッシュするデコレータ。
+///
+/// `HttpClient`と同じtraitを実装するため、`Reqwest`や`InmemClient`の上に透過的に重ねられ、
+/// `get_trades`などの呼び出し側は変更する必要がない。キャッシュのTTLはデフォルト値
+/// (`default_ttl`)に加えて、`with_override`で渡したURLプレフィックスごとに上書きできる。
+/// 更新頻度が高いデータには短いTTLを、リファレンスデータのような更新頻度が低いデータには
+/// 長いTTLを与えられる。
+pub struct CachingHttpClient<C: HttpClient> {
+    inner: C,
+    default_ttl: Duration,
+    overrides: HashMap<String, Duration>,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl<C: HttpClient> CachingHttpClient<C> {
+    /// デフォルトのTTLを指定してキャッシュ付きクライアントを作成する。
+    pub fn new(inner: C, default_ttl: Duration) -> CachingHttpClient<C> {
+        CachingHttpClient {
+            inner,
+            default_ttl,
+            overrides: HashMap::new(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `url_prefix`で始まるURLに対するTTLを上書きする。
+    pub fn with_override(mut self, url_prefix: &str, ttl: Duration) -> CachingHttpClient<C> {
+        self.overrides.insert(url_prefix.to_string(), ttl);
+        self
+    }
+
+    /// `url`にマッチする`overrides`のうち、最も長い(=最も具体的な)プレフィックスのTTLを返す。
+    /// `HashMap`の反復順序は不定なので、`find`で最初に見つかったものを使うのではなく、
+    /// 常に最長一致を明示的に選ぶ。
+    fn ttl_for(&self, url: &str) -> Duration {
+        self.overrides
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient + Sync> HttpClient for CachingHttpClient<C> {
+    async fn get(&self, url: String, headers: &Headers) -> Result<RawResponse, Error> {
+        if let Some(entry) = self.cache.lock().unwrap().get(&url) {
+            if entry.expires_at > Instant::now() {
+                return Ok(entry.response.clone());
+            }
+        }
+
+        let response = self.inner.get(url.clone(), headers).await?;
+        self.cache.lock().unwrap().insert(
+            url.clone(),
+            CacheEntry {
+                response: response.clone(),
+                expires_at: Instant::now() + self.ttl_for(&url),
+            },
+        );
+        Ok(response)
+    }
+
+    // POSTは冪等とは限らないのでキャッシュせず、常に`inner`へ委譲する。
+    async fn post(
+        &self,
+        url: String,
+        headers: &Headers,
+        body: String,
+    ) -> Result<RawResponse, Error> {
+        self.inner.post(url, headers, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// `get`が呼ばれた回数を数える、キャッシュヒットを検証するためのテスト用クライアント。
+    struct CountingClient {
+        body_text: String,
+        call_count: AtomicU32,
+    }
+
+    #[async_trait]
+    impl HttpClient for CountingClient {
+        async fn get(&self, _url: String, _headers: &Headers) -> Result<RawResponse, Error> {
+            self.call_count.fetch_add(1, Ordering::SeqCst);
+            Ok(RawResponse {
+                http_status_code: 200,
+                body_text: self.body_text.clone(),
+            })
+        }
+
+        async fn post(
+            &self,
+            _url: String,
+            _headers: &Headers,
+            _body: String,
+        ) -> Result<RawResponse, Error> {
+            unimplemented!("not used in these tests")
+        }
+    }
+
+    #[tokio::test]
+    async fn a_second_get_within_the_ttl_is_served_from_the_cache() {
+        let inner = CountingClient {
+            body_text: "cached".to_string(),
+            call_count: AtomicU32::new(0),
+        };
+        let client = CachingHttpClient::new(inner, Duration::from_secs(60));
+        let headers = Headers::create_empty_headers();
+
+        let first = client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+        let second = client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+
+        assert_eq!(first.body_text, "cached");
+        assert_eq!(second.body_text, "cached");
+        assert_eq!(client.inner.call_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn a_get_after_the_ttl_expires_delegates_to_the_inner_client_again() {
+        let inner = CountingClient {
+            body_text: "cached".to_string(),
+            call_count: AtomicU32::new(0),
+        };
+        let client = CachingHttpClient::new(inner, Duration::from_millis(0));
+        let headers = Headers::create_empty_headers();
+
+        client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+        client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+
+        assert_eq!(client.inner.call_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn a_per_endpoint_override_governs_expiry_instead_of_the_default_ttl() {
+        let inner = CountingClient {
+            body_text: "cached".to_string(),
+            call_count: AtomicU32::new(0),
+        };
+        // デフォルトTTLは長いが、`/v1/trades`向けのオーバーライドは即座に失効するので、
+        // オーバーライドが効いていれば2回目のgetは`inner`に委譲されるはず。
+        let client = CachingHttpClient::new(inner, Duration::from_secs(60))
+            .with_override("https://example.com/v1/trades", Duration::from_millis(0));
+        let headers = Headers::create_empty_headers();
+
+        client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+        client
+            .get("https://example.com/v1/trades".to_string(), &headers)
+            .await
+            .unwrap();
+
+        assert_eq!(client.inner.call_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn the_longest_matching_override_prefix_wins() {
+        // 広いプレフィックス`/v1`は長いTTL、より具体的な`/v1/trades`は即座に失効するTTL。
+        // どちらも`https://example.com/v1/trades`にマッチするが、より長い(具体的な)
+        // `/v1/trades`が優先されなければならない。登録順を逆にしても結果が変わらないことも
+        // 確認する(`HashMap`の反復順序に依存していないことの確認)。
+        for register_specific_first in [false, true] {
+            let inner = CountingClient {
+                body_text: "cached".to_string(),
+                call_count: AtomicU32::new(0),
+            };
+            let client = CachingHttpClient::new(inner, Duration::from_secs(60));
+            let client = if register_specific_first {
+                client
+                    .with_override("https://example.com/v1/trades", Duration::from_millis(0))
+                    .with_override("https://example.com/v1", Duration::from_secs(60))
+            } else {
+                client
+                    .with_override("https://example.com/v1", Duration::from_secs(60))
+                    .with_override("https://example.com/v1/trades", Duration::from_millis(0))
+            };
+            let headers = Headers::create_empty_headers();
+
+            client
+                .get("https://example.com/v1/trades".to_string(), &headers)
+                .await
+                .unwrap();
+            client
+                .get("https://example.com/v1/trades".to_string(), &headers)
+                .await
+                .unwrap();
+
+            assert_eq!(client.inner.call_count.load(Ordering::SeqCst), 2);
+        }
+    }
+}
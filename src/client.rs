@@ -0,0 +1,64 @@
+//! APIクライアントの設定を定義する。
+
+use crate::end_point::PUBLIC_ENDPOINT;
+use crate::http_client::HttpClient;
+
+/// `HttpClient`とAPIのベースURLをまとめて保持するクライアント。
+///
+/// エンドポイント関数はこの構造体が保持する`base_url`を使ってリクエスト先を組み立てる。
+/// グローバルな定数の代わりにベースURLを差し替えられるので、モックサーバーやサンドボックス
+/// 環境に向けて統合テストを実行できる。
+pub struct GmoClient<C: HttpClient> {
+    http_client: C,
+    base_url: String,
+}
+
+impl<C: HttpClient> GmoClient<C> {
+    /// Public APIのデフォルトのベースURL(`PUBLIC_ENDPOINT`)を使うクライアントを作成する。
+    pub fn new(http_client: C) -> GmoClient<C> {
+        GmoClient::with_base_url(http_client, PUBLIC_ENDPOINT.to_string())
+    }
+
+    /// 任意のベースURLを指定してクライアントを作成する。モックサーバーや別環境に向けて
+    /// テストする場合に使う。
+    pub fn with_base_url(http_client: C, base_url: String) -> GmoClient<C> {
+        GmoClient {
+            http_client,
+            base_url,
+        }
+    }
+
+    pub fn http_client(&self) -> &C {
+        &self.http_client
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::tests::InmemClient;
+
+    fn dummy_http_client() -> InmemClient {
+        InmemClient {
+            http_status_code: 200,
+            body_text: String::new(),
+            return_error: false,
+        }
+    }
+
+    #[test]
+    fn new_uses_the_public_endpoint_as_the_default_base_url() {
+        let client = GmoClient::new(dummy_http_client());
+        assert_eq!(client.base_url(), PUBLIC_ENDPOINT);
+    }
+
+    #[test]
+    fn with_base_url_overrides_the_default_base_url() {
+        let client = GmoClient::with_base_url(dummy_http_client(), "http://localhost:8080".to_string());
+        assert_eq!(client.base_url(), "http://localhost:8080");
+    }
+}
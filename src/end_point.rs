@@ -0,0 +1,7 @@
+//! APIのエンドポイントを定義する。
+
+/// Public APIのエンドポイント。
+pub const PUBLIC_ENDPOINT: &str = "https://api.coin.z.com/public";
+
+/// Private APIのエンドポイント。認証が必要なAPIはこちらを使う。
+pub const PRIVATE_ENDPOINT: &str = "https://api.coin.z.com/private";
@@ -0,0 +1,19 @@
+//! クレート共通のエラー型を定義する。
+
+use thiserror::Error as ThisError;
+
+/// クレート全体で使うエラー型。
+#[derive(ThisError, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    ParseError(#[from] url::ParseError),
+
+    #[error("unknown error")]
+    UnknownError {},
+}
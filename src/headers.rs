@@ -0,0 +1,33 @@
+//! HTTPリクエストヘッダーを定義する。
+
+use std::collections::HashMap;
+
+/// HTTPリクエストに付与するヘッダー。
+#[derive(Clone, Default)]
+pub struct Headers {
+    values: HashMap<String, String>,
+}
+
+impl Headers {
+    /// 空のヘッダーを作成する。Public APIなど認証が不要なリクエストで使う。
+    pub fn create_empty_headers() -> Headers {
+        Headers {
+            values: HashMap::new(),
+        }
+    }
+
+    /// ヘッダーを1つ追加する。
+    pub fn insert(&mut self, key: &str, value: String) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    /// キーに対応するヘッダーの値を取得する。
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.values.get(key)
+    }
+
+    /// 登録されているヘッダーを走査する。
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.values.iter()
+    }
+}
@@ -1,27 +1,71 @@
 //! HTTPクライアントを定義する。
 
 use crate::error::*;
+use crate::headers::Headers;
 use crate::response::*;
 use async_trait::async_trait;
 
 /// HTTPクライアントのtrait。GET, POSTとか。
 #[async_trait]
 pub trait HttpClient {
-    async fn get(&self, url: String) -> Result<RawResponse, Error>;
+    async fn get(&self, url: String, headers: &Headers) -> Result<RawResponse, Error>;
+    async fn post(&self, url: String, headers: &Headers, body: String)
+        -> Result<RawResponse, Error>;
 }
 
 /// ネットワークアクセス時に用いるHttpクライアント。
 /// Rustではreqwestがデファクトっぽいのでネットワークアクセスするときはreqwestを使う。
 pub struct Reqwest;
 
+impl Reqwest {
+    fn build_headers(headers: &Headers) -> reqwest::header::HeaderMap {
+        let mut header_map = reqwest::header::HeaderMap::new();
+        for (key, value) in headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+                reqwest::header::HeaderValue::from_str(value),
+            ) {
+                header_map.insert(name, value);
+            }
+        }
+        header_map
+    }
+}
+
 #[async_trait]
 impl HttpClient for Reqwest {
-    async fn get(&self, url: String) -> Result<RawResponse, Error> {
-        // ここでunwrapを使うとエラーが起きた時にPanicになるが、どうやってそれを回避すればいいのかがわからない。
-        // ParseErrorをcrate::Errorに変換したいけどどうやってやるんだ？
-        let url_as_reqwest_style = reqwest::Url::parse(&url).unwrap();
+    async fn get(&self, url: String, headers: &Headers) -> Result<RawResponse, Error> {
+        let url_as_reqwest_style = reqwest::Url::parse(&url)?;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(url_as_reqwest_style)
+            .headers(Self::build_headers(headers))
+            .send()
+            .await?;
+        let status_code = response.status().as_u16();
+        let body = response.text().await?;
+        Ok(RawResponse {
+            http_status_code: (status_code),
+            body_text: (body),
+        })
+    }
 
-        let response = reqwest::get(url_as_reqwest_style).await?;
+    async fn post(
+        &self,
+        url: String,
+        headers: &Headers,
+        body: String,
+    ) -> Result<RawResponse, Error> {
+        let url_as_reqwest_style = reqwest::Url::parse(&url)?;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(url_as_reqwest_style)
+            .headers(Self::build_headers(headers))
+            .body(body)
+            .send()
+            .await?;
         let status_code = response.status().as_u16();
         let body = response.text().await?;
         Ok(RawResponse {
@@ -44,7 +88,7 @@ pub mod tests {
 
     #[async_trait]
     impl HttpClient for InmemClient {
-        async fn get(&self, _url: String) -> Result<RawResponse, Error> {
+        async fn get(&self, _url: String, _headers: &Headers) -> Result<RawResponse, Error> {
             if (self.return_error) {
                 return Err(Error::UnknownError {});
             }
@@ -54,5 +98,28 @@ pub mod tests {
                 body_text: (self.body_text.clone()),
             })
         }
+
+        async fn post(
+            &self,
+            _url: String,
+            _headers: &Headers,
+            _body: String,
+        ) -> Result<RawResponse, Error> {
+            if (self.return_error) {
+                return Err(Error::UnknownError {});
+            }
+
+            Ok(RawResponse {
+                http_status_code: (self.http_status_code),
+                body_text: (self.body_text.clone()),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn get_returns_a_parse_error_instead_of_panicking_on_a_malformed_url() {
+        let headers = Headers::create_empty_headers();
+        let result = Reqwest.get("not a valid url".to_string(), &headers).await;
+        assert!(matches!(result, Err(Error::ParseError(_))));
     }
 }
@@ -0,0 +1,41 @@
+//! JSONのデシリアライズで使う補助関数を定義する。
+//! GMOコインのAPIは数値を文字列で返すフィールドが多いため、文字列から変換するヘルパーを用意する。
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{de, Deserialize, Deserializer};
+
+/// 文字列として返ってくる数値をi64に変換する。JSONの数値として返ってくる場合(`pagination`など)も受け付ける。
+pub fn str_to_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StrOrI64 {
+        Str(String),
+        Num(i64),
+    }
+    match StrOrI64::deserialize(deserializer)? {
+        StrOrI64::Str(s) => s.parse::<i64>().map_err(de::Error::custom),
+        StrOrI64::Num(n) => Ok(n),
+    }
+}
+
+/// 文字列として返ってくる数値をf64に変換する。
+pub fn str_to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse::<f64>().map_err(de::Error::custom)
+}
+
+/// GMOコインのタイムスタンプ文字列(例: `2019-03-28T09:28:07.980Z`)をchronoの`DateTime<Utc>`に変換する。
+pub fn gmo_timestamp_to_chrono_timestamp<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Utc.datetime_from_str(&s, "%Y-%m-%dT%H:%M:%S%.3fZ")
+        .map_err(de::Error::custom)
+}
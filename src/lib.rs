@@ -0,0 +1,13 @@
+//! GMOコインのREST APIクライアント。
+
+pub mod caching_http_client;
+pub mod client;
+pub mod end_point;
+pub mod error;
+pub mod headers;
+pub mod http_client;
+pub mod json;
+pub mod private;
+pub mod public;
+pub mod response;
+pub mod symbol;
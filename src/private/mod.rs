@@ -0,0 +1,169 @@
+//! Private API(認証が必要なAPI)を実装する。
+
+pub mod signature;
+
+use crate::error::Error;
+use crate::headers::Headers;
+use crate::http_client::HttpClient;
+use crate::response::RawResponse;
+use async_trait::async_trait;
+
+/// APIキーとAPIシークレットを保持し、Private APIが要求する`API-KEY`・`API-TIMESTAMP`・
+/// `API-SIGN`ヘッダーを自動的に付与する`HttpClient`のラッパー。
+///
+/// 内部に保持する`HttpClient`に対して透過的に振る舞うため、`Reqwest`をそのまま渡せば
+/// ネットワークアクセスする一方、テストでは`InmemClient`を渡せる。
+pub struct AuthenticatedClient<C: HttpClient> {
+    http_client: C,
+    api_key: String,
+    api_secret: String,
+}
+
+impl<C: HttpClient> AuthenticatedClient<C> {
+    /// APIキーとAPIシークレットから認証済みクライアントを作成する。
+    pub fn new(http_client: C, api_key: String, api_secret: String) -> AuthenticatedClient<C> {
+        AuthenticatedClient {
+            http_client,
+            api_key,
+            api_secret,
+        }
+    }
+
+    /// 渡された`Headers`に署名用の3つのヘッダーを追加したものを返す。
+    fn sign_headers(&self, method: &str, url: &str, body: &str, headers: &Headers) -> Headers {
+        let mut signed_headers = headers.clone();
+        let path = signature::path_from_url(url);
+        let timestamp = signature::now_as_millis_string();
+        let sign = signature::sign(&self.api_secret, &timestamp, method, &path, body);
+        signed_headers.insert("API-KEY", self.api_key.clone());
+        signed_headers.insert("API-TIMESTAMP", timestamp);
+        signed_headers.insert("API-SIGN", sign);
+        signed_headers
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient + Sync> HttpClient for AuthenticatedClient<C> {
+    async fn get(&self, url: String, headers: &Headers) -> Result<RawResponse, Error> {
+        let signed_headers = self.sign_headers("GET", &url, "", headers);
+        self.http_client.get(url, &signed_headers).await
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: &Headers,
+        body: String,
+    ) -> Result<RawResponse, Error> {
+        let signed_headers = self.sign_headers("POST", &url, &body, headers);
+        self.http_client.post(url, &signed_headers, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// `inner`に渡された最後の`Headers`を記録する、ヘッダー注入を検証するためのテスト用クライアント。
+    #[derive(Default)]
+    struct SpyClient {
+        last_get_headers: Mutex<Option<Headers>>,
+        last_post_headers: Mutex<Option<Headers>>,
+    }
+
+    #[async_trait]
+    impl HttpClient for SpyClient {
+        async fn get(&self, _url: String, headers: &Headers) -> Result<RawResponse, Error> {
+            *self.last_get_headers.lock().unwrap() = Some(headers.clone());
+            Ok(RawResponse {
+                http_status_code: 200,
+                body_text: String::new(),
+            })
+        }
+
+        async fn post(
+            &self,
+            _url: String,
+            headers: &Headers,
+            _body: String,
+        ) -> Result<RawResponse, Error> {
+            *self.last_post_headers.lock().unwrap() = Some(headers.clone());
+            Ok(RawResponse {
+                http_status_code: 200,
+                body_text: String::new(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn get_injects_api_key_timestamp_and_sign_headers() {
+        let client = AuthenticatedClient::new(
+            SpyClient::default(),
+            "my-api-key".to_string(),
+            "my-api-secret".to_string(),
+        );
+
+        client
+            .get(
+                "https://api.coin.z.com/private/v1/account/margin".to_string(),
+                &Headers::create_empty_headers(),
+            )
+            .await
+            .unwrap();
+
+        let headers = client
+            .http_client
+            .last_get_headers
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap();
+        assert_eq!(headers.get("API-KEY"), Some(&"my-api-key".to_string()));
+        let timestamp = headers.get("API-TIMESTAMP").unwrap();
+        let expected_sign = signature::sign(
+            "my-api-secret",
+            timestamp,
+            "GET",
+            "/private/v1/account/margin",
+            "",
+        );
+        assert_eq!(headers.get("API-SIGN"), Some(&expected_sign));
+    }
+
+    #[tokio::test]
+    async fn post_injects_api_key_timestamp_and_sign_headers_derived_from_the_body() {
+        let client = AuthenticatedClient::new(
+            SpyClient::default(),
+            "my-api-key".to_string(),
+            "my-api-secret".to_string(),
+        );
+
+        client
+            .post(
+                "https://api.coin.z.com/private/v1/order".to_string(),
+                &Headers::create_empty_headers(),
+                r#"{"symbol":"BTC"}"#.to_string(),
+            )
+            .await
+            .unwrap();
+
+        let headers = client
+            .http_client
+            .last_post_headers
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap();
+        assert_eq!(headers.get("API-KEY"), Some(&"my-api-key".to_string()));
+        let timestamp = headers.get("API-TIMESTAMP").unwrap();
+        let expected_sign = signature::sign(
+            "my-api-secret",
+            timestamp,
+            "POST",
+            "/private/v1/order",
+            r#"{"symbol":"BTC"}"#,
+        );
+        assert_eq!(headers.get("API-SIGN"), Some(&expected_sign));
+    }
+}
@@ -0,0 +1,79 @@
+//! Private APIのリクエスト署名(`API-SIGN`ヘッダー)を計算する。
+
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 現在時刻をミリ秒単位のUNIXタイムスタンプ文字列として返す。`API-TIMESTAMP`ヘッダーに使う。
+pub fn now_as_millis_string() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the unix epoch")
+        .as_millis();
+    millis.to_string()
+}
+
+/// `timestamp + method + path + body`をAPIシークレットでHMAC-SHA256署名し、16進文字列で返す。
+pub fn sign(api_secret: &str, timestamp: &str, method: &str, path: &str, body: &str) -> String {
+    let message = format!("{}{}{}{}", timestamp, method, path, body);
+    let mut mac =
+        HmacSha256::new_from_slice(api_secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// URLからホストとクエリ文字列を除いたパスを取り出す。GMOコインの署名はクエリ文字列を含めない
+/// パスに対して計算するため(クエリ文字列は実際のHTTPリクエストにのみ使う)、ここで取り除く。
+pub fn path_from_url(url: &str) -> String {
+    let without_scheme = url.splitn(2, "://").nth(1).unwrap_or(url);
+    let with_query = match without_scheme.find('/') {
+        Some(index) => &without_scheme[index..],
+        None => "",
+    };
+    match with_query.find('?') {
+        Some(index) => with_query[..index].to_string(),
+        None => with_query.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_from_url_strips_scheme_and_host() {
+        let url = "https://api.coin.z.com/private/v1/account/margin";
+        assert_eq!(path_from_url(url), "/private/v1/account/margin");
+    }
+
+    #[test]
+    fn path_from_url_strips_the_query_string() {
+        let url = "https://api.coin.z.com/public/v1/trades?symbol=BTC&page=1&count=100";
+        assert_eq!(path_from_url(url), "/public/v1/trades");
+    }
+
+    #[test]
+    fn sign_is_deterministic_and_hex_encoded() {
+        let signature = sign("api-secret", "1538108668", "POST", "/v1/order", "{}");
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            signature,
+            sign("api-secret", "1538108668", "POST", "/v1/order", "{}")
+        );
+    }
+
+    #[test]
+    fn sign_matches_a_known_answer() {
+        // `echo -n '1538108668POST/v1/order{}' | openssl dgst -sha256 -hmac "api-secret" -hex`
+        // で計算した既知の値と一致することを確認する。引数の順序を取り違えるリファクタリングを
+        // 検出するため、決定性や桁数だけでなく実際のダイジェスト値を固定してテストする。
+        let signature = sign("api-secret", "1538108668", "POST", "/v1/order", "{}");
+        assert_eq!(
+            signature,
+            "857c1a34c87f2eac5bf7e154e89d1e71c645bb90375a712278d66a0463562cf3"
+        );
+    }
+}
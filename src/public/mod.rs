@@ -0,0 +1,3 @@
+//! Public API(認証不要なAPI)を実装する。
+
+pub mod trades;
@@ -1,6 +1,6 @@
 //! 取引履歴APIを実装する。
 
-use crate::end_point::*;
+use crate::client::GmoClient;
 use crate::error::Error;
 use crate::headers::Headers;
 use crate::http_client::*;
@@ -8,11 +8,57 @@ use crate::json::*;
 use crate::response::*;
 use crate::symbol::*;
 use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream};
 use serde::Deserialize;
+use std::collections::VecDeque;
 
 /// 取引履歴APIのパス。
 const TRADES_API_PATH: &str = "/v1/trades";
 
+/// 取引履歴APIの`count`に指定できる最大値。
+const TRADES_COUNT_MAX: u32 = 1000;
+
+/// 取引履歴APIのページングオプション。`page`・`count`ともデフォルトでは未設定で、
+/// クエリ文字列にも載せない。その場合サーバー側のデフォルト(`page=1`, `count=100`)が使われる。
+/// `TradesOptions::default().page(2).count(50)`のようにメソッドチェーンで組み立てる。
+#[derive(Default)]
+pub struct TradesOptions {
+    page: Option<u32>,
+    count: Option<u32>,
+}
+
+impl TradesOptions {
+    /// 取得するページ番号を指定する。
+    pub fn page(mut self, page: u32) -> TradesOptions {
+        self.page = Some(page);
+        self
+    }
+
+    /// 1ページあたりの取得件数を指定する。APIが許容する最大値(`TRADES_COUNT_MAX`)を超える値は
+    /// 最大値に丸める。
+    pub fn count(mut self, count: u32) -> TradesOptions {
+        self.count = Some(count.min(TRADES_COUNT_MAX));
+        self
+    }
+
+    /// 設定されているフィールドのみをクエリ文字列に変換する。未設定のフィールドは省略し、
+    /// サーバー側のデフォルト値にフォールバックさせる。
+    fn to_query_string(&self) -> String {
+        let mut params = vec![];
+        if let Some(page) = self.page {
+            params.push(format!("page={}", page));
+        }
+        if let Some(count) = self.count {
+            params.push(format!("count={}", count));
+        }
+        params
+            .iter()
+            .map(|param| format!("&{}", param))
+            .collect::<Vec<_>>()
+            .join("")
+    }
+}
+
 /// 取引履歴APIから返ってくるレスポンスのうち取引データ(price, side, size, timestamp)を格納する構造体。
 #[derive(Deserialize)]
 pub struct Trade {
@@ -61,36 +107,114 @@ impl RestResponse<Trades> {
     }
 }
 
-/// 取引履歴APIを呼び出す。引数で取得対象ページと1ページ当たりの取得件数を指定する。
+/// 取引履歴APIを呼び出す。`options`で取得対象ページと1ページ当たりの取得件数を指定する。
+/// リクエスト先は`client`が保持するベースURLを使って組み立てるため、サンドボックス環境や
+/// モックサーバーに向けてテストできる。
 pub async fn get_trades_with_options(
-    http_client: &impl HttpClient,
+    client: &GmoClient<impl HttpClient>,
     symbol: &Symbol,
-    page: i32,
-    count: i32,
+    options: &TradesOptions,
 ) -> Result<RestResponse<Trades>, Error> {
     let url = format!(
-        "{}{}?symbol={}&page={}&count={}",
-        PUBLIC_ENDPOINT,
+        "{}{}?symbol={}{}",
+        client.base_url(),
         TRADES_API_PATH,
         to_string(&symbol),
-        page,
-        count,
+        options.to_query_string(),
     );
     let headers = Headers::create_empty_headers();
-    let response = http_client.get(url, &headers).await?;
+    let response = client.http_client().get(url, &headers).await?;
     parse_from_http_response::<Trades>(&response)
 }
 
-/// 取引履歴APIを呼び出す。
+/// 取引履歴APIを呼び出す。ページングはサーバー側のデフォルト(`page=1`, `count=100`)に任せる。
 pub async fn get_trades(
-    http_client: &impl HttpClient,
+    client: &GmoClient<impl HttpClient>,
     symbol: &Symbol,
 ) -> Result<RestResponse<Trades>, Error> {
-    get_trades_with_options(http_client, &symbol, 1, 100).await
+    get_trades_with_options(client, &symbol, &TradesOptions::default()).await
+}
+
+/// ページングを内部で管理する`trades_stream`の状態。
+struct TradesStreamState<'a, C: HttpClient> {
+    client: &'a GmoClient<C>,
+    symbol: Symbol,
+    count: u32,
+    current_page: u32,
+    buffer: VecDeque<Trade>,
+    exhausted: bool,
+}
+
+/// 取引履歴APIを全ページにわたって取得し続ける`Stream`を返す。
+///
+/// `count`件ずつ`page`をインクリメントしながら`get_trades_with_options`を呼び出し、
+/// 取得した`Trade`を1件ずつ`Item`として流す。1ページの取得件数が`count`未満になるか、
+/// `pagination.currentPage`が`pagination.count`(総ページ数)に達した時点で終了する。
+/// 呼び出し側はページングを意識せず`while let Some(trade) = stream.next().await`で
+/// 取引履歴を走査できる。
+pub fn trades_stream<'a, C>(
+    client: &'a GmoClient<C>,
+    symbol: Symbol,
+    count: u32,
+) -> impl Stream<Item = Result<Trade, Error>> + 'a
+where
+    C: HttpClient,
+{
+    let state = TradesStreamState {
+        client,
+        symbol,
+        // `TradesOptions::count`と同じ上限で丸めておく。ここで丸めないと、サーバーが実際に
+        // 返すページサイズ(最大`TRADES_COUNT_MAX`)より大きい`count`を渡した際に、
+        // 「1ページの取得件数が`count`未満」という終了条件が常に真になり、1ページ目で
+        // ストリームが終了してしまう。
+        count: count.min(TRADES_COUNT_MAX),
+        current_page: 1,
+        buffer: VecDeque::new(),
+        exhausted: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(trade) = state.buffer.pop_front() {
+                return Some((Ok(trade), state));
+            }
+
+            if state.exhausted {
+                return None;
+            }
+
+            let options = TradesOptions::default()
+                .page(state.current_page)
+                .count(state.count);
+            let response =
+                get_trades_with_options(state.client, &state.symbol, &options).await;
+
+            match response {
+                Ok(resp) => {
+                    let current_page = resp.body.data.pagination.currentPage;
+                    let total_pages = resp.body.data.pagination.count;
+                    let trades = resp.body.data.list;
+                    let fetched = trades.len() as i64;
+                    state.buffer.extend(trades);
+
+                    if fetched < state.count as i64 || current_page >= total_pages {
+                        state.exhausted = true;
+                    } else {
+                        state.current_page += 1;
+                    }
+                }
+                Err(error) => {
+                    state.exhausted = true;
+                    return Some((Err(error), state));
+                }
+            }
+        }
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::client::GmoClient;
     use crate::http_client::tests::InmemClient;
     use crate::public::trades::*;
     use crate::symbol::Symbol;
@@ -126,12 +250,12 @@ mod tests {
     #[tokio::test]
     async fn should_return_ok_when_http_client_returns_correct_response() {
         let body = TRADES_RESPONSE_SAMPLE;
-        let http_client = InmemClient {
+        let client = GmoClient::new(InmemClient {
             http_status_code: 200,
             body_text: body.to_string(),
             return_error: false,
-        };
-        let resp = get_trades(&http_client, &Symbol::Btc).await.unwrap();
+        });
+        let resp = get_trades(&client, &Symbol::Btc).await.unwrap();
         assert_eq!(resp.http_status_code, 200);
         assert_eq!(resp.body.status, 0);
         assert_eq!(
@@ -145,4 +269,109 @@ mod tests {
         let trades = resp.trades();
         assert_eq!(trades.len(), 2);
     }
+
+    #[tokio::test]
+    async fn trades_stream_yields_every_trade_and_stops_on_a_short_page() {
+        use futures::StreamExt;
+
+        let body = TRADES_RESPONSE_SAMPLE;
+        let client = GmoClient::new(InmemClient {
+            http_status_code: 200,
+            body_text: body.to_string(),
+            return_error: false,
+        });
+        let mut stream = trades_stream(&client, Symbol::Btc, 100);
+
+        let mut trades = vec![];
+        while let Some(trade) = stream.next().await {
+            trades.push(trade.unwrap());
+        }
+
+        assert_eq!(trades.len(), 2);
+    }
+
+    /// ページ番号に応じて異なるレスポンスを返すテスト用クライアント。`trades_stream`が
+    /// 実際に複数ページをまたいで取得することを検証するために使う。
+    struct PagedClient {
+        page_bodies: Vec<String>,
+    }
+
+    fn page_param(url: &str) -> usize {
+        url.split('&')
+            .find_map(|param| param.strip_prefix("page="))
+            .and_then(|page| page.parse::<usize>().ok())
+            .unwrap_or(1)
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for PagedClient {
+        async fn get(&self, url: String, _headers: &Headers) -> Result<RawResponse, Error> {
+            let page = page_param(&url);
+            Ok(RawResponse {
+                http_status_code: 200,
+                body_text: self.page_bodies[page - 1].clone(),
+            })
+        }
+
+        async fn post(
+            &self,
+            _url: String,
+            _headers: &Headers,
+            _body: String,
+        ) -> Result<RawResponse, Error> {
+            unimplemented!("not used in these tests")
+        }
+    }
+
+    fn trades_page_body(current_page: i64, total_pages: i64, trade_count: usize) -> String {
+        let trade = r#"{"price":"750760","side":"BUY","size":"0.1","timestamp":"2018-03-30T12:34:56.789Z"}"#;
+        let list = vec![trade; trade_count].join(",");
+        format!(
+            r#"{{"status":0,"data":{{"pagination":{{"currentPage":{},"count":{}}},"list":[{}]}}, "responsetime":"2019-03-28T09:28:07.980Z"}}"#,
+            current_page, total_pages, list
+        )
+    }
+
+    #[tokio::test]
+    async fn trades_stream_keeps_fetching_further_pages_when_count_is_clamped() {
+        use futures::StreamExt;
+
+        // ページ1はクランプ後の上限(TRADES_COUNT_MAX)ぴったりの件数を返す満杯のページ、
+        // ページ2は端数だけを返す最後のページ。呼び出し時に渡す`count`(5000)は
+        // `TRADES_COUNT_MAX`より大きいので、内部ではクランプされた値と比較しなければ
+        // ページ1で「短いページ」と誤判定されてしまう。
+        let client = GmoClient::new(PagedClient {
+            page_bodies: vec![
+                trades_page_body(1, 2, TRADES_COUNT_MAX as usize),
+                trades_page_body(2, 2, 3),
+            ],
+        });
+        let mut stream = trades_stream(&client, Symbol::Btc, 5000);
+
+        let mut trades = vec![];
+        while let Some(trade) = stream.next().await {
+            trades.push(trade.unwrap());
+        }
+
+        assert_eq!(trades.len(), TRADES_COUNT_MAX as usize + 3);
+    }
+
+    #[test]
+    fn trades_options_omits_unset_fields_from_the_query_string() {
+        assert_eq!(TradesOptions::default().to_query_string(), "");
+        assert_eq!(TradesOptions::default().page(2).to_query_string(), "&page=2");
+        assert_eq!(
+            TradesOptions::default().page(2).count(50).to_query_string(),
+            "&page=2&count=50"
+        );
+    }
+
+    #[test]
+    fn trades_options_count_is_capped_at_the_api_maximum() {
+        let options = TradesOptions::default().count(TRADES_COUNT_MAX + 1);
+        assert_eq!(
+            options.to_query_string(),
+            format!("&count={}", TRADES_COUNT_MAX)
+        );
+    }
 }
@@ -0,0 +1,29 @@
+//! HTTPレスポンスを格納する構造体を定義する。
+
+use crate::error::Error;
+use serde::de::DeserializeOwned;
+
+/// HTTPクライアントが返す生のレスポンス。
+#[derive(Clone)]
+pub struct RawResponse {
+    pub http_status_code: u16,
+    pub body_text: String,
+}
+
+/// APIのレスポンスをパースした結果を格納する構造体。
+pub struct RestResponse<T> {
+    pub http_status_code: u16,
+    pub body: T,
+}
+
+/// `RawResponse`をパースして`RestResponse<T>`に変換する。
+pub fn parse_from_http_response<T>(response: &RawResponse) -> Result<RestResponse<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    let body = serde_json::from_str::<T>(&response.body_text)?;
+    Ok(RestResponse {
+        http_status_code: response.http_status_code,
+        body,
+    })
+}
@@ -0,0 +1,21 @@
+//! GMOコインが取り扱う銘柄(通貨ペア)を定義する。
+
+/// GMOコインが取り扱う銘柄。
+pub enum Symbol {
+    Btc,
+    EthJpy,
+    Bch,
+    Ltc,
+    Xrp,
+}
+
+/// 銘柄をAPIが要求するクエリ文字列表現に変換する。
+pub fn to_string(symbol: &Symbol) -> &'static str {
+    match symbol {
+        Symbol::Btc => "BTC",
+        Symbol::EthJpy => "ETH_JPY",
+        Symbol::Bch => "BCH",
+        Symbol::Ltc => "LTC",
+        Symbol::Xrp => "XRP",
+    }
+}